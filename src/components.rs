@@ -79,8 +79,107 @@ impl From<ChannelMixing> for Mat3 {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TonemappingOperator {
+    None,
+    Reinhard,
+    ReinhardLuminance,
+    AcesFitted,
+    AgX,
+}
+
+impl Default for TonemappingOperator {
+    fn default() -> Self {
+        TonemappingOperator::None
+    }
+}
+
 #[derive(Debug, Clone)]
-pub struct NormalTonemapping;
+pub struct Tonemapping {
+    pub operator: TonemappingOperator,
+    pub exposure: f32,
+}
+
+impl Default for Tonemapping {
+    fn default() -> Self {
+        Self {
+            operator: TonemappingOperator::default(),
+            exposure: 1.0,
+        }
+    }
+}
 
+/// Screen-space depth/normal edge-detection outline, composited by the uber
+/// pass after tonemapping. Requires the view's depth texture, and falls back
+/// to depth-only edges if no normal prepass texture is present for the view.
 #[derive(Debug, Clone)]
-pub struct ACESTonemapping;
\ No newline at end of file
+pub struct Outline {
+    pub depth_threshold: f32,
+    pub normal_threshold: f32,
+    pub outline_color: Color,
+    /// Spreads the edge-detection taps further apart (in texels), widening
+    /// the resulting outline. `1.0` samples immediate neighbors.
+    pub thickness: f32,
+}
+
+impl Default for Outline {
+    fn default() -> Self {
+        Self {
+            depth_threshold: 0.01,
+            normal_threshold: 0.4,
+            outline_color: Color::BLACK,
+            thickness: 1.0,
+        }
+    }
+}
+
+/// Selects how the upscale pass reconstructs full resolution from a
+/// `RenderScale`-reduced internal target.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpscaleFilter {
+    Nearest,
+    Bilinear,
+    Sharpen,
+}
+
+impl Default for UpscaleFilter {
+    fn default() -> Self {
+        UpscaleFilter::Bilinear
+    }
+}
+
+/// Runs the uber pass (and its bloom pyramid) at a reduced internal
+/// resolution, then upscales to the camera's full resolution as the last step
+/// of the post-process subgraph. The scene itself is still rendered at full
+/// resolution; this does not change the size of the scene's target. `scale` of `1.0` (the default) renders at full
+/// resolution; the upscale pass still runs to move the result into the
+/// view's target, but is just a same-size blit at that point.
+#[derive(Debug, Clone, Copy)]
+pub struct RenderScale {
+    pub scale: f32,
+    pub filter: UpscaleFilter,
+}
+
+impl Default for RenderScale {
+    fn default() -> Self {
+        Self {
+            scale: 1.0,
+            filter: UpscaleFilter::default(),
+        }
+    }
+}
+
+/// Selects the uber pass's intermediate target format: full HDR
+/// (`Rgba16Float`, the default) so bloom thresholding against emissive
+/// values above 1.0 is physically meaningful, or an LDR `Rgba8UnormSrgb`
+/// fallback for cameras that don't need it.
+#[derive(Debug, Clone, Copy)]
+pub struct Hdr {
+    pub enabled: bool,
+}
+
+impl Default for Hdr {
+    fn default() -> Self {
+        Self { enabled: true }
+    }
+}
\ No newline at end of file
@@ -1,20 +1,20 @@
 use crate::components::*;
 use bevy::{
     asset::{AssetServer, Handle},
-    ecs::{prelude::*, system::SystemState},
+    ecs::prelude::*,
     math::*,
     pbr2::{ExtractedMeshes, PbrShaders},
     render2::{
-        camera::{ActiveCameras, CameraPlugin},
+        camera::ActiveCameras,
         color::Color,
         render_asset::RenderAssets,
         render_graph::{Node, NodeRunError, RenderGraphContext, SlotInfo, SlotType},
-        render_phase::{Draw, DrawFunctions, Drawable, RenderPhase, TrackedRenderPass},
+        render_phase::{DrawFunctionId, DrawFunctions, PhaseItem, RenderCommand, RenderPhase, TrackedRenderPass},
         render_resource::*,
         renderer::{RenderContext, RenderDevice},
         shader::Shader,
         texture::*,
-        view::{ExtractedView, ViewMeta, ViewUniformOffset},
+        view::{ExtractedView, ViewDepthTexture, ViewMeta, ViewTarget},
     },
 };
 use crevice::std140::AsStd140;
@@ -24,9 +24,8 @@ bitflags::bitflags!{
     #[derive(AsStd140)]
     pub struct UberFlags: u32 {
         const BLOOM = 1 << 0;
-        const NORMAL_TONEMAPPING = 1 << 1;
-        const ACES_TONEMAPPING = 1 << 2;
-        const CHANNEL_MIXING = 1 << 3;
+        const CHANNEL_MIXING = 1 << 1;
+        const OUTLINE = 1 << 2;
     }
 }
 
@@ -34,12 +33,23 @@ bitflags::bitflags!{
 pub struct UberUniform {
     flags: UberFlags,
     bloom: UberBloom,
+    tonemapping: UberTonemapping,
     channel_mixing: UberChannelMixing,
+    outline: UberOutline,
 }
 
+// Ratio of `threshold` used as the width of the soft-knee region in the
+// bloom prefilter's quadratic threshold curve.
+const BLOOM_KNEE_RATIO: f32 = 0.5;
+
+// Mip pyramids bottom out once a level would be smaller than this on its
+// shortest axis, so no zero-sized mips are ever allocated.
+const BLOOM_MIN_MIP_SIZE: u32 = 2;
+
 #[derive(Debug, Clone, Default, AsStd140)]
 struct UberBloom {
     threshold: f32,
+    knee: f32,
     intensity: f32,
     scatter: f32,
     tint: Vec4,
@@ -50,6 +60,7 @@ impl From<Bloom> for UberBloom {
     fn from(value: Bloom) -> Self {
         Self {
             threshold: value.threshold,
+            knee: value.threshold * BLOOM_KNEE_RATIO,
             intensity: value.intensity,
             scatter: value.scatter,
             tint: Vec4::from(value.tint),
@@ -58,6 +69,31 @@ impl From<Bloom> for UberBloom {
     }
 }
 
+/// Computes how many mip levels a bloom pyramid should have for a view of
+/// the given size, so that the smallest mip is still at least
+/// `BLOOM_MIN_MIP_SIZE` on its shortest axis.
+fn bloom_mip_count(width: u32, height: u32) -> u32 {
+    let min_dimension = width.min(height).max(BLOOM_MIN_MIP_SIZE);
+    ((min_dimension / BLOOM_MIN_MIP_SIZE) as f32).log2().floor() as u32 + 1
+}
+
+#[derive(Debug, Clone, Default, AsStd140)]
+struct UberTonemapping {
+    // Index of the tonemapping curve `uber.wgsl` should apply; mirrors the
+    // declaration order of `TonemappingOperator`.
+    operator: u32,
+    exposure: f32,
+}
+
+impl From<Tonemapping> for UberTonemapping {
+    fn from(value: Tonemapping) -> Self {
+        Self {
+            operator: value.operator as u32,
+            exposure: value.exposure,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Default, AsStd140)]
 struct UberChannelMixing {
     matrix: Mat3,
@@ -71,10 +107,94 @@ impl From<ChannelMixing> for UberChannelMixing {
     }
 }
 
+#[derive(Debug, Clone, Default, AsStd140)]
+struct UberOutline {
+    depth_threshold: f32,
+    normal_threshold: f32,
+    color: Vec4,
+    thickness: f32,
+}
+
+impl From<Outline> for UberOutline {
+    fn from(value: Outline) -> Self {
+        Self {
+            depth_threshold: value.depth_threshold,
+            normal_threshold: value.normal_threshold,
+            color: Vec4::from(value.outline_color),
+            thickness: value.thickness,
+        }
+    }
+}
+
 pub struct UberEffectShaders {
-    pipeline: RenderPipeline,
-    view_layout: BindGroupLayout,
+    // Built for both the HDR and LDR-fallback targets up front, since a
+    // camera's `Hdr` toggle is per-view and this resource is shared; the
+    // draw function picks one of the two per `UberPhase` item.
+    pipeline_hdr: RenderPipeline,
+    pipeline_ldr: RenderPipeline,
+    scene_layout: BindGroupLayout,
+    config_layout: BindGroupLayout,
     sampler: Sampler,
+    // Non-filtering sampler for the outline stage's depth input; wgpu
+    // requires a sampler's filter mode to match its bind group layout entry.
+    depth_sampler: Sampler,
+}
+
+/// The `ViewUber`/uber pipeline target format for a camera: full HDR
+/// (`Rgba16Float`) so bloom thresholding against emissive values above 1.0
+/// is physically meaningful, or an LDR fallback for cameras that don't need
+/// it, per the camera's `Hdr` component.
+fn uber_target_format(hdr: bool) -> TextureFormat {
+    if hdr {
+        TextureFormat::Rgba16Float
+    } else {
+        TextureFormat::Rgba8UnormSrgb
+    }
+}
+
+fn texture_sampler_entries(start_binding: u32) -> [BindGroupLayoutEntry; 2] {
+    [
+        BindGroupLayoutEntry {
+            binding: start_binding,
+            visibility: ShaderStage::FRAGMENT,
+            ty: BindingType::Texture {
+                sample_type: TextureSampleType::Float { filterable: true },
+                view_dimension: TextureViewDimension::D2,
+                multisampled: false,
+            },
+            count: None,
+        },
+        BindGroupLayoutEntry {
+            binding: start_binding + 1,
+            visibility: ShaderStage::FRAGMENT,
+            ty: BindingType::Sampler(SamplerBindingType::Filtering),
+            count: None,
+        },
+    ]
+}
+
+// Depth textures can't be sampled with a filtering sampler, so the outline
+// stage's depth input gets its own entry pair distinct from
+// `texture_sampler_entries`.
+fn depth_sampler_entries(start_binding: u32) -> [BindGroupLayoutEntry; 2] {
+    [
+        BindGroupLayoutEntry {
+            binding: start_binding,
+            visibility: ShaderStage::FRAGMENT,
+            ty: BindingType::Texture {
+                sample_type: TextureSampleType::Depth,
+                view_dimension: TextureViewDimension::D2,
+                multisampled: false,
+            },
+            count: None,
+        },
+        BindGroupLayoutEntry {
+            binding: start_binding + 1,
+            visibility: ShaderStage::FRAGMENT,
+            ty: BindingType::Sampler(SamplerBindingType::NonFiltering),
+            count: None,
+        },
+    ]
 }
 
 // TODO: this pattern for initializing the shaders / pipeline isn't ideal. this should be handled by the asset system
@@ -85,18 +205,43 @@ impl FromWorld for UberEffectShaders {
         let uber_shader = Shader::from_wgsl(include_str!("uber.wgsl"));
         let uber_shader_module = render_device.create_shader_module(&uber_shader);
 
-        let view_layout = render_device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+        // group 0: the HDR scene color being post-processed, plus the view's
+        // depth texture and (optional, placeholder-backed) normal prepass
+        // texture that the outline edge-detection stage samples.
+        let scene_color_entries = texture_sampler_entries(0);
+        let scene_depth_entries = depth_sampler_entries(2);
+        let scene_normal_entries = texture_sampler_entries(4);
+        let scene_layout = render_device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            entries: &[
+                scene_color_entries[0],
+                scene_color_entries[1],
+                scene_depth_entries[0],
+                scene_depth_entries[1],
+                scene_normal_entries[0],
+                scene_normal_entries[1],
+            ],
+            label: None,
+        });
+
+        // group 1: the uber config uniform plus the composited bloom texture.
+        let bloom_entries = texture_sampler_entries(1);
+        let config_layout = render_device.create_bind_group_layout(&BindGroupLayoutDescriptor {
             entries: &[
                 BindGroupLayoutEntry {
                     binding: 0,
                     visibility: ShaderStage::FRAGMENT,
                     ty: BindingType::Buffer {
                         ty: BufferBindingType::Uniform,
-                        has_dynamic_offset: false,
+                        // One slot of the buffer per view;
+                        // `SetUberConfigBindGroup` resolves which slot at bind
+                        // time via the view's `UberUniformOffset`.
+                        has_dynamic_offset: true,
                         min_binding_size: BufferSize::new(std::mem::size_of::<UberUniform>() as u64),
                     },
                     count: None,
                 },
+                bloom_entries[0],
+                bloom_entries[1],
             ],
             label: None,
         });
@@ -104,78 +249,238 @@ impl FromWorld for UberEffectShaders {
         let pipeline_layout = render_device.create_pipeline_layout(&PipelineLayoutDescriptor {
             label: None,
             push_constant_ranges: &[],
-            bind_group_layouts: &[&view_layout],
+            bind_group_layouts: &[&scene_layout, &config_layout],
         });
 
-        let pipeline = render_device.create_render_pipeline(&RenderPipelineDescriptor {
-            label: None,
-            vertex: VertexState {
-                buffers: &[],
-                module: &uber_shader_module,
-                entry_point: "vertex",
-            },
-            fragment: Some(FragmentState {
-                module: &uber_shader_module,
-                entry_point: "fragment",
-                targets: &[ColorTargetState {
-                    format: TextureFormat::R8Unorm,
-                    blend: Some(BlendState {
-                        color: BlendComponent {
-                            src_factor: BlendFactor::Src,
-                            dst_factor: BlendFactor::OneMinusSrc,
-                            operation: BlendOperation::Add,
-                        },
-                        alpha: BlendComponent {
-                            src_factor: BlendFactor::One,
-                            dst_factor: BlendFactor::One,
-                            operation: BlendOperation::Add,
-                        },
-                    }),
-                    write_mask: ColorWrite::ALL,
-                }],
+        let make_pipeline = |format: TextureFormat| {
+            render_device.create_render_pipeline(&RenderPipelineDescriptor {
+                label: None,
+                vertex: VertexState {
+                    buffers: &[],
+                    module: &uber_shader_module,
+                    entry_point: "vertex",
+                },
+                fragment: Some(FragmentState {
+                    module: &uber_shader_module,
+                    entry_point: "fragment",
+                    // The uber pass writes into a target that was just
+                    // cleared, not composited onto existing contents, so this
+                    // is a plain overwrite; any blend state here multiplies
+                    // the output against the clear color instead of leaving
+                    // it untouched.
+                    targets: &[ColorTargetState {
+                        format,
+                        blend: None,
+                        write_mask: ColorWrite::ALL,
+                    }],
+                }),
+                depth_stencil: None,
+                layout: Some(&pipeline_layout),
+                multisample: MultisampleState::default(),
+                primitive: PrimitiveState {
+                    topology: PrimitiveTopology::TriangleList,
+                    strip_index_format: None,
+                    front_face: FrontFace::Ccw,
+                    cull_mode: Some(Face::Back),
+                    polygon_mode: PolygonMode::Fill,
+                    clamp_depth: false,
+                    conservative: false,
+                },
+            })
+        };
+
+        UberEffectShaders {
+            pipeline_hdr: make_pipeline(uber_target_format(true)),
+            pipeline_ldr: make_pipeline(uber_target_format(false)),
+            scene_layout,
+            config_layout,
+            sampler: render_device.create_sampler(&SamplerDescriptor::default()),
+            depth_sampler: render_device.create_sampler(&SamplerDescriptor {
+                mag_filter: FilterMode::Nearest,
+                min_filter: FilterMode::Nearest,
+                mipmap_filter: FilterMode::Nearest,
+                ..SamplerDescriptor::default()
             }),
-            depth_stencil: None,
-            layout: Some(&pipeline_layout),
-            multisample: MultisampleState::default(),
-            primitive: PrimitiveState {
-                topology: PrimitiveTopology::TriangleList,
-                strip_index_format: None,
-                front_face: FrontFace::Ccw,
-                cull_mode: Some(Face::Back),
-                polygon_mode: PolygonMode::Fill,
-                clamp_depth: false,
-                conservative: false,
-            },
+        }
+    }
+}
+
+/// Pipelines for the three bloom pyramid passes (prefilter, downsample,
+/// upsample). All three share the same input layout: a source texture, a
+/// sampler, and the `UberUniform` buffer (for `threshold`/`knee`/`scatter`).
+pub struct BloomEffectShaders {
+    prefilter_pipeline: RenderPipeline,
+    downsample_pipeline: RenderPipeline,
+    upsample_pipeline: RenderPipeline,
+    input_layout: BindGroupLayout,
+    sampler: Sampler,
+}
+
+impl FromWorld for BloomEffectShaders {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.get_resource::<RenderDevice>().unwrap();
+
+        let bloom_shader = Shader::from_wgsl(include_str!("bloom.wgsl"));
+        let bloom_shader_module = render_device.create_shader_module(&bloom_shader);
+
+        let input_entries = texture_sampler_entries(0);
+        let input_layout = render_device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            entries: &[
+                input_entries[0],
+                input_entries[1],
+                BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: ShaderStage::FRAGMENT,
+                    ty: BindingType::Buffer {
+                        ty: BufferBindingType::Uniform,
+                        has_dynamic_offset: true,
+                        min_binding_size: BufferSize::new(std::mem::size_of::<UberUniform>() as u64),
+                    },
+                    count: None,
+                },
+            ],
+            label: None,
         });
 
-        UberEffectShaders {
-            pipeline,
-            view_layout,
+        let pipeline_layout = render_device.create_pipeline_layout(&PipelineLayoutDescriptor {
+            label: None,
+            push_constant_ranges: &[],
+            bind_group_layouts: &[&input_layout],
+        });
+
+        let primitive = PrimitiveState {
+            topology: PrimitiveTopology::TriangleList,
+            strip_index_format: None,
+            front_face: FrontFace::Ccw,
+            cull_mode: Some(Face::Back),
+            polygon_mode: PolygonMode::Fill,
+            clamp_depth: false,
+            conservative: false,
+        };
+
+        let make_pipeline = |entry_point: &'static str, blend: Option<BlendState>| {
+            render_device.create_render_pipeline(&RenderPipelineDescriptor {
+                label: None,
+                vertex: VertexState {
+                    buffers: &[],
+                    module: &bloom_shader_module,
+                    entry_point: "vertex",
+                },
+                fragment: Some(FragmentState {
+                    module: &bloom_shader_module,
+                    entry_point,
+                    targets: &[ColorTargetState {
+                        format: TextureFormat::Rgba16Float,
+                        blend,
+                        write_mask: ColorWrite::ALL,
+                    }],
+                }),
+                depth_stencil: None,
+                layout: Some(&pipeline_layout),
+                multisample: MultisampleState::default(),
+                primitive,
+            })
+        };
+
+        BloomEffectShaders {
+            prefilter_pipeline: make_pipeline("prefilter", None),
+            downsample_pipeline: make_pipeline("downsample", None),
+            // The upsample pass additively blends each tent-filtered level
+            // into the next-larger mip, which already holds that mip's
+            // downsampled content.
+            upsample_pipeline: make_pipeline(
+                "upsample",
+                Some(BlendState {
+                    color: BlendComponent {
+                        src_factor: BlendFactor::One,
+                        dst_factor: BlendFactor::One,
+                        operation: BlendOperation::Add,
+                    },
+                    alpha: BlendComponent {
+                        src_factor: BlendFactor::One,
+                        dst_factor: BlendFactor::One,
+                        operation: BlendOperation::Add,
+                    },
+                }),
+            ),
+            input_layout,
             sampler: render_device.create_sampler(&SamplerDescriptor::default()),
         }
     }
 }
 
-type ExtractedUberUniform = UberUniform;
+impl UberUniform {
+    /// Builds the GPU-facing uniform for a single camera from whichever of
+    /// the post-process components it has; an absent component disables its
+    /// effect's flag and falls back to that effect's `Default`. Tonemapping
+    /// has no flag of its own since `TonemappingOperator::None` is already a
+    /// no-op branch in `uber.wgsl`.
+    fn from_camera(
+        bloom: Option<&Bloom>,
+        tonemapping: Option<&Tonemapping>,
+        channel_mixing: Option<&ChannelMixing>,
+        outline: Option<&Outline>,
+    ) -> Self {
+        let mut flags = UberFlags::empty();
+        flags.set(UberFlags::BLOOM, bloom.is_some());
+        flags.set(UberFlags::CHANNEL_MIXING, channel_mixing.is_some());
+        flags.set(UberFlags::OUTLINE, outline.is_some());
+
+        Self {
+            flags,
+            bloom: bloom.cloned().unwrap_or_default().into(),
+            tonemapping: tonemapping.cloned().unwrap_or_default().into(),
+            channel_mixing: channel_mixing.cloned().unwrap_or_default().into(),
+            outline: outline.cloned().unwrap_or_default().into(),
+        }
+    }
+}
 
+/// Extracts post-process settings per-camera instead of from a single global
+/// resource, so each active camera (e.g. a UI camera and a 3D camera) can
+/// carry its own bloom/tonemapping/channel-mixing/outline configuration.
 pub fn extract_uber(
     mut commands: Commands,
     active_cameras: Res<ActiveCameras>,
-    uber_config: Res<UberUniform>,
+    cameras: Query<(
+        Option<&Bloom>,
+        Option<&Tonemapping>,
+        Option<&ChannelMixing>,
+        Option<&Outline>,
+    )>,
 ) {
-    if let Some(camera_3d) = active_cameras.get(CameraPlugin::CAMERA_3D) {
-        if let Some(entity) = camera_3d.entity {
-            commands
-                .get_or_spawn(entity)
-                .insert(RenderPhase::<UberPhase>::default());
+    for active_camera in active_cameras.iter() {
+        let entity = match active_camera.entity {
+            Some(entity) => entity,
+            None => continue,
+        };
+        let (bloom, tonemapping, channel_mixing, outline) = match cameras.get(entity) {
+            Ok(components) => components,
+            Err(_) => continue,
+        };
+
+        // A camera with none of these components (e.g. a UI camera) wants no
+        // part of the uber pass; give it the full bloom pyramid + uber chain
+        // only when it's actually configured to use one.
+        if bloom.is_none() && tonemapping.is_none() && channel_mixing.is_none() && outline.is_none() {
+            continue;
         }
+
+        let uniform = UberUniform::from_camera(bloom, tonemapping, channel_mixing, outline);
+
+        commands
+            .get_or_spawn(entity)
+            .insert(RenderPhase::<UberPhase>::default())
+            .insert(uniform);
     }
-    commands.insert_resource::<ExtractedUberUniform>(uber_config.clone());
 }
 
 #[derive(Default)]
 pub struct UberMeta {
-    pub uniform: UniformVec<UberUniform>,
+    // `DynamicUniformVec` (not `UniformVec`) so `push`'s returned offset is
+    // aligned to the device's dynamic-offset requirement, matching the
+    // `has_dynamic_offset: true` binding in `UberEffectShaders::from_world`.
+    pub uniform: DynamicUniformVec<UberUniform>,
 }
 
 pub struct ViewUber {
@@ -183,32 +488,174 @@ pub struct ViewUber {
     pub view_uber_texture_view: TextureView,
 }
 
+/// The view's normal prepass target, sampled by the outline stage alongside
+/// the view's depth texture. Normally provided by a normal prepass elsewhere
+/// in the app; views without one get a 1x1 placeholder from `prepare_uber`
+/// so the scene bind group layout is the same whether or not a normal
+/// prepass ran, and the outline stage just degrades to depth-only edges.
+pub struct ViewNormalTexture {
+    pub texture_view: TextureView,
+}
+
+/// The view's depth texture, as sampled by the outline stage. Cameras with no
+/// `ViewDepthTexture` (e.g. a UI/2D camera) still get one of these, backed by
+/// a 1x1 placeholder from `prepare_uber`, so the scene bind group layout is
+/// unconditional and bloom/tonemapping keep running for views that just have
+/// nothing for the outline stage to read.
+pub struct ViewOutlineDepthTexture {
+    pub texture_view: TextureView,
+}
+
+/// The bloom mip pyramid for a single view: a downsample chain running from
+/// full resolution down to `BLOOM_MIN_MIP_SIZE`, and an upsample chain one
+/// level shorter that holds the progressively composited result.
+pub struct ViewBloomTextures {
+    downsample_texture: Texture,
+    downsample_mip_views: Vec<TextureView>,
+    upsample_texture: Texture,
+    upsample_mip_views: Vec<TextureView>,
+    // Pixel size of each downsample mip, used to size the copies that seed
+    // each upsample level before the tent filter is additively blended in.
+    mip_extents: Vec<Extent3d>,
+}
+
+fn create_mip_views(texture: &Texture, mip_count: u32) -> Vec<TextureView> {
+    (0..mip_count)
+        .map(|mip| {
+            texture.create_view(&TextureViewDescriptor {
+                label: None,
+                format: None,
+                dimension: Some(TextureViewDimension::D2),
+                aspect: TextureAspect::All,
+                base_mip_level: mip,
+                mip_level_count: NonZeroU32::new(1),
+                base_array_layer: 0,
+                array_layer_count: NonZeroU32::new(1),
+            })
+        })
+        .collect()
+}
+
 pub fn prepare_uber(
     mut commands: Commands,
-    extracted_uber_config: Res<ExtractedUberUniform>,
     mut uber_meta: ResMut<UberMeta>,
     mut texture_cache: ResMut<TextureCache>,
     render_device: Res<RenderDevice>,
-    views: Query<(Entity, &ExtractedView), With<RenderPhase<UberPhase>>>,
+    views: Query<
+        (
+            Entity,
+            &ExtractedView,
+            &UberUniform,
+            Option<&ViewDepthTexture>,
+            Option<&ViewNormalTexture>,
+            Option<&RenderScale>,
+            Option<&Hdr>,
+        ),
+        With<RenderPhase<UberPhase>>,
+    >,
 ) {
-    uber_meta.uniform.reserve_and_clear(1, &render_device);
-    uber_meta.uniform.push(extracted_uber_config.clone().into());
+    uber_meta
+        .uniform
+        .reserve_and_clear(views.iter().count(), &render_device);
 
     // set up uber for each view
-    for (entity, view) in views.iter() {
+    for (entity, view, uniform, depth_texture, normal_texture, render_scale, hdr) in views.iter() {
+        let offset = uber_meta.uniform.push(uniform.clone());
+        commands.entity(entity).insert(UberUniformOffset { offset });
+
+        // The uber pass and bloom pyramid render at this (possibly reduced)
+        // resolution; the scene texture they read is not resized here.
+        // `UpscalePassNode` is responsible for blitting the result back up to
+        // the view's actual resolution.
+        let scale = render_scale.map(|s| s.scale).unwrap_or(1.0).clamp(0.1, 1.0);
+        let width = ((view.width as f32) * scale).round().max(1.0) as u32;
+        let height = ((view.height as f32) * scale).round().max(1.0) as u32;
+
+        if normal_texture.is_none() {
+            let placeholder_normal_texture = texture_cache.get(
+                &render_device,
+                TextureDescriptor {
+                    label: Some("outline_placeholder_normal_texture"),
+                    size: Extent3d {
+                        width: 1,
+                        height: 1,
+                        depth_or_array_layers: 1,
+                    },
+                    mip_level_count: 1,
+                    sample_count: 1,
+                    dimension: TextureDimension::D2,
+                    format: TextureFormat::Rgba16Float,
+                    usage: TextureUsage::SAMPLED,
+                },
+            );
+            let placeholder_normal_texture_view =
+                placeholder_normal_texture
+                    .texture
+                    .create_view(&TextureViewDescriptor {
+                        label: None,
+                        format: None,
+                        dimension: Some(TextureViewDimension::D2),
+                        aspect: TextureAspect::All,
+                        base_mip_level: 0,
+                        mip_level_count: None,
+                        base_array_layer: 0,
+                        array_layer_count: NonZeroU32::new(1),
+                    });
+            commands.entity(entity).insert(ViewNormalTexture {
+                texture_view: placeholder_normal_texture_view,
+            });
+        }
+
+        let outline_depth_texture_view = match depth_texture {
+            Some(depth_texture) => depth_texture.view.clone(),
+            None => {
+                let placeholder_depth_texture = texture_cache.get(
+                    &render_device,
+                    TextureDescriptor {
+                        label: Some("outline_placeholder_depth_texture"),
+                        size: Extent3d {
+                            width: 1,
+                            height: 1,
+                            depth_or_array_layers: 1,
+                        },
+                        mip_level_count: 1,
+                        sample_count: 1,
+                        dimension: TextureDimension::D2,
+                        format: TextureFormat::Depth32Float,
+                        usage: TextureUsage::SAMPLED,
+                    },
+                );
+                placeholder_depth_texture
+                    .texture
+                    .create_view(&TextureViewDescriptor {
+                        label: None,
+                        format: None,
+                        dimension: Some(TextureViewDimension::D2),
+                        aspect: TextureAspect::DepthOnly,
+                        base_mip_level: 0,
+                        mip_level_count: None,
+                        base_array_layer: 0,
+                        array_layer_count: NonZeroU32::new(1),
+                    })
+            }
+        };
+        commands.entity(entity).insert(ViewOutlineDepthTexture {
+            texture_view: outline_depth_texture_view,
+        });
+
         let view_uber_texture = texture_cache.get(
             &render_device,
             TextureDescriptor {
                 label: None,
                 size: Extent3d {
-                    width: view.width,
-                    height: view.height,
+                    width,
+                    height,
                     depth_or_array_layers: 1,
                 },
                 mip_level_count: 1,
                 sample_count: 1,
                 dimension: TextureDimension::D2,
-                format: TextureFormat::R8Unorm,
+                format: uber_target_format(hdr.map(|h| h.enabled).unwrap_or(true)),
                 usage: TextureUsage::RENDER_ATTACHMENT | TextureUsage::SAMPLED,
             },
         );
@@ -229,78 +676,291 @@ pub fn prepare_uber(
             view_uber_texture: view_uber_texture.texture,
             view_uber_texture_view,
         });
+
+        let mip_count = bloom_mip_count(width, height);
+        let downsample_texture = texture_cache.get(
+            &render_device,
+            TextureDescriptor {
+                label: Some("bloom_downsample_texture"),
+                size: Extent3d {
+                    width,
+                    height,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: mip_count,
+                sample_count: 1,
+                dimension: TextureDimension::D2,
+                format: TextureFormat::Rgba16Float,
+                // `COPY_SRC` so the upsample chain's seed copy (in
+                // `UberPassNode::run`) can read each mip as a copy source.
+                usage: TextureUsage::RENDER_ATTACHMENT | TextureUsage::SAMPLED | TextureUsage::COPY_SRC,
+            },
+        );
+        let upsample_texture = texture_cache.get(
+            &render_device,
+            TextureDescriptor {
+                label: Some("bloom_upsample_texture"),
+                size: Extent3d {
+                    width: (width >> 1).max(1),
+                    height: (height >> 1).max(1),
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: (mip_count - 1).max(1),
+                sample_count: 1,
+                dimension: TextureDimension::D2,
+                format: TextureFormat::Rgba16Float,
+                // `COPY_DST` so the upsample chain's seed copy can write into
+                // each mip before the tent filter pass additively blends onto it.
+                usage: TextureUsage::RENDER_ATTACHMENT | TextureUsage::SAMPLED | TextureUsage::COPY_DST,
+            },
+        );
+        let downsample_mip_views = create_mip_views(&downsample_texture.texture, mip_count);
+        let upsample_mip_views = create_mip_views(&upsample_texture.texture, mip_count - 1);
+        let mip_extents = (0..mip_count)
+            .map(|mip| Extent3d {
+                width: (width >> mip).max(1),
+                height: (height >> mip).max(1),
+                depth_or_array_layers: 1,
+            })
+            .collect();
+
+        commands.entity(entity).insert(ViewBloomTextures {
+            downsample_texture: downsample_texture.texture,
+            downsample_mip_views,
+            upsample_texture: upsample_texture.texture,
+            upsample_mip_views,
+            mip_extents,
+        });
     }
 
     uber_meta.uniform.write_to_staging_buffer(&render_device);
 }
 
-pub struct UberViewBindGroup {
-    view_bind_group: BindGroup,
+/// This view's byte offset into the shared, multi-view `UberMeta::uniform`
+/// buffer, analogous to `ViewUniformOffset` for the main view uniforms.
+pub struct UberUniformOffset {
+    pub offset: u32,
 }
 
+pub struct UberSceneBindGroup {
+    scene_bind_group: BindGroup,
+}
+
+// The config bind group also carries the view's composited bloom texture
+// (group 1, binding 1/2 in `uber.wgsl`), so it's per-view rather than a
+// single global resource.
 pub struct UberConfigBindGroup {
     uber_config_bind_group: BindGroup,
 }
 
+/// Per-view bind groups for every stage of the bloom pyramid: one prefilter
+/// bind group, one downsample bind group per step down the pyramid, and one
+/// upsample bind group per step back up.
+pub struct ViewBloomBindGroups {
+    prefilter_bind_group: BindGroup,
+    downsample_bind_groups: Vec<BindGroup>,
+    upsample_bind_groups: Vec<BindGroup>,
+}
+
+fn create_bloom_input_bind_group(
+    render_device: &RenderDevice,
+    bloom_shaders: &BloomEffectShaders,
+    uber_meta: &UberMeta,
+    input_view: &TextureView,
+) -> BindGroup {
+    render_device.create_bind_group(&BindGroupDescriptor {
+        entries: &[
+            BindGroupEntry {
+                binding: 0,
+                resource: BindingResource::TextureView(input_view),
+            },
+            BindGroupEntry {
+                binding: 1,
+                resource: BindingResource::Sampler(&bloom_shaders.sampler),
+            },
+            BindGroupEntry {
+                binding: 2,
+                resource: uber_meta.uniform.binding(),
+            },
+        ],
+        label: None,
+        layout: &bloom_shaders.input_layout,
+    })
+}
+
 pub fn queue_meshes(
     mut commands: Commands,
-    draw_functions: Res<DrawFunctions>,
+    draw_functions: Res<DrawFunctions<UberPhase>>,
     render_device: Res<RenderDevice>,
     uber_shaders: Res<UberEffectShaders>,
+    bloom_shaders: Res<BloomEffectShaders>,
     _pbr_shaders: Res<PbrShaders>,
     view_meta: Res<ViewMeta>,
     uber_meta: Res<UberMeta>,
-    _extracted_uber_config: Res<ExtractedUberUniform>,
     _gpu_images: Res<RenderAssets<Image>>,
-    mut views: Query<(Entity, &mut RenderPhase<UberPhase>)>,
+    mut views: Query<(
+        Entity,
+        &ViewTarget,
+        &ViewOutlineDepthTexture,
+        &ViewNormalTexture,
+        &ViewBloomTextures,
+        Option<&Hdr>,
+        &mut RenderPhase<UberPhase>,
+    )>,
 ) {
     if view_meta.uniforms.len() < 1 {
         return;
     }
 
-    let uber_config_bind_group = render_device.create_bind_group(&BindGroupDescriptor {
-        entries: &[BindGroupEntry {
-            binding: 0,
-            resource: uber_meta.uniform.binding(),
-        }],
-        label: None,
-        layout: &uber_shaders.view_layout,
-    });
+    for (entity, view_target, depth_texture, normal_texture, bloom_textures, hdr, mut uber_phase) in
+        views.iter_mut()
+    {
+        let hdr = hdr.map(|h| h.enabled).unwrap_or(true);
+        let scene_bind_group = render_device.create_bind_group(&BindGroupDescriptor {
+            entries: &[
+                BindGroupEntry {
+                    binding: 0,
+                    resource: BindingResource::TextureView(view_target.main_texture()),
+                },
+                BindGroupEntry {
+                    binding: 1,
+                    resource: BindingResource::Sampler(&uber_shaders.sampler),
+                },
+                BindGroupEntry {
+                    binding: 2,
+                    resource: BindingResource::TextureView(&depth_texture.texture_view),
+                },
+                BindGroupEntry {
+                    binding: 3,
+                    resource: BindingResource::Sampler(&uber_shaders.depth_sampler),
+                },
+                BindGroupEntry {
+                    binding: 4,
+                    resource: BindingResource::TextureView(&normal_texture.texture_view),
+                },
+                BindGroupEntry {
+                    binding: 5,
+                    resource: BindingResource::Sampler(&uber_shaders.sampler),
+                },
+            ],
+            label: None,
+            layout: &uber_shaders.scene_layout,
+        });
 
-    commands.insert_resource(UberConfigBindGroup {
-        uber_config_bind_group,
-    });
+        commands
+            .entity(entity)
+            .insert(UberSceneBindGroup { scene_bind_group });
 
-    for (i, (entity, mut uber_phase)) in views.iter_mut().enumerate() {
-        // TODO: cache this?
-        let view_bind_group = render_device.create_bind_group(&BindGroupDescriptor {
+        // The top of the upsample chain (mip 0) holds the fully composited
+        // bloom result; with only one mip there's no upsample chain, so fall
+        // back to the prefiltered downsample mip directly.
+        let bloom_result_view = bloom_textures
+            .upsample_mip_views
+            .get(0)
+            .unwrap_or(&bloom_textures.downsample_mip_views[0]);
+        let uber_config_bind_group = render_device.create_bind_group(&BindGroupDescriptor {
             entries: &[
                 BindGroupEntry {
                     binding: 0,
-                    resource: view_meta.uniforms.binding(),
+                    resource: uber_meta.uniform.binding(),
+                },
+                BindGroupEntry {
+                    binding: 1,
+                    resource: BindingResource::TextureView(bloom_result_view),
+                },
+                BindGroupEntry {
+                    binding: 2,
+                    resource: BindingResource::Sampler(&uber_shaders.sampler),
                 },
             ],
             label: None,
-            layout: &uber_shaders.view_layout,
+            layout: &uber_shaders.config_layout,
         });
 
         commands
             .entity(entity)
-            .insert(UberViewBindGroup { view_bind_group });
+            .insert(UberConfigBindGroup {
+                uber_config_bind_group,
+            });
+
+        let mip_count = bloom_textures.downsample_mip_views.len() as u32;
+        let prefilter_bind_group = create_bloom_input_bind_group(
+            &render_device,
+            &bloom_shaders,
+            &uber_meta,
+            view_target.main_texture(),
+        );
+        let downsample_bind_groups = (0..mip_count - 1)
+            .map(|mip| {
+                create_bloom_input_bind_group(
+                    &render_device,
+                    &bloom_shaders,
+                    &uber_meta,
+                    &bloom_textures.downsample_mip_views[mip as usize],
+                )
+            })
+            .collect::<Vec<_>>();
+        let upsample_bind_groups = (0..mip_count - 1)
+            .map(|mip| {
+                // The smallest upsample step reads from the downsample
+                // chain's last (smallest) mip; every other step reads the
+                // previous (smaller) upsample level.
+                let input_view = if mip == mip_count - 2 {
+                    &bloom_textures.downsample_mip_views[(mip_count - 1) as usize]
+                } else {
+                    &bloom_textures.upsample_mip_views[(mip + 1) as usize]
+                };
+                create_bloom_input_bind_group(&render_device, &bloom_shaders, &uber_meta, input_view)
+            })
+            .collect::<Vec<_>>();
+
+        commands.entity(entity).insert(ViewBloomBindGroups {
+            prefilter_bind_group,
+            downsample_bind_groups,
+            upsample_bind_groups,
+        });
 
-        let draw_uber = draw_functions.read().get_id::<DrawUber>().unwrap();
-        uber_phase.add(Drawable {
-            draw_function: draw_uber,
-            draw_key: i,
-            sort_key: 0,
+        let draw_function = draw_functions.read().get_id::<DrawUber>().unwrap();
+        uber_phase.add(UberPhase {
+            entity,
+            draw_function,
+            hdr,
         });
     }
 }
 
-pub struct UberPhase;
+/// The uber pass's single `PhaseItem`: one fullscreen draw per view, so
+/// there's nothing meaningful to sort by.
+pub struct UberPhase {
+    entity: Entity,
+    draw_function: DrawFunctionId,
+    // Whether this view's `Hdr` component selects the HDR or LDR-fallback
+    // uber pipeline; read by `SetUberPipeline`.
+    hdr: bool,
+}
+
+impl PhaseItem for UberPhase {
+    type SortKey = ();
+
+    fn entity(&self) -> Entity {
+        self.entity
+    }
+
+    fn sort_key(&self) -> Self::SortKey {}
+
+    fn draw_function(&self) -> DrawFunctionId {
+        self.draw_function
+    }
+}
 
 pub struct UberPassNode {
-    main_view_query: QueryState<(&'static ViewUber, &'static RenderPhase<UberPhase>)>,
+    main_view_query: QueryState<(
+        &'static ViewUber,
+        &'static ViewBloomTextures,
+        &'static ViewBloomBindGroups,
+        &'static UberUniformOffset,
+        &'static RenderPhase<UberPhase>,
+    )>,
 }
 
 impl UberPassNode {
@@ -313,6 +973,34 @@ impl UberPassNode {
     }
 }
 
+fn run_fullscreen_pass(
+    render_context: &mut RenderContext,
+    label: &'static str,
+    target: &TextureView,
+    load: LoadOp<Color>,
+    pipeline: &RenderPipeline,
+    bind_group: &BindGroup,
+    dynamic_offsets: &[u32],
+) {
+    let pass_descriptor = RenderPassDescriptor {
+        label: Some(label),
+        color_attachments: &[RenderPassColorAttachment {
+            view: target,
+            resolve_target: None,
+            ops: Operations { load, store: true },
+        }],
+        depth_stencil_attachment: None,
+    };
+
+    let render_pass = render_context
+        .command_encoder
+        .begin_render_pass(&pass_descriptor);
+    let mut tracked_pass = TrackedRenderPass::new(render_pass);
+    tracked_pass.set_render_pipeline(pipeline);
+    tracked_pass.set_bind_group(0, bind_group, dynamic_offsets);
+    tracked_pass.draw(0..3, 0..1);
+}
+
 impl Node for UberPassNode {
     fn input(&self) -> Vec<SlotInfo> {
         vec![SlotInfo::new(Self::IN_VIEW, SlotType::Entity)]
@@ -334,9 +1022,72 @@ impl Node for UberPassNode {
             .write_to_uniform_buffer(&mut render_context.command_encoder);
 
         let view_entity = graph.get_input_entity(Self::IN_VIEW)?;
-        if let Some((view_uber, uber_phase)) =
+        if let Some((view_uber, bloom_textures, bloom_bind_groups, uber_uniform_offset, uber_phase)) =
             self.main_view_query.get_manual(world, view_entity).ok()
         {
+            let bloom_shaders = world.get_resource::<BloomEffectShaders>().unwrap();
+            let mip_count = bloom_textures.downsample_mip_views.len() as u32;
+            let dynamic_offsets = [uber_uniform_offset.offset];
+
+            // Prefilter: isolate bright samples from the scene into mip 0.
+            run_fullscreen_pass(
+                render_context,
+                "bloom_prefilter",
+                &bloom_textures.downsample_mip_views[0],
+                LoadOp::Clear(Color::BLACK.into()),
+                &bloom_shaders.prefilter_pipeline,
+                &bloom_bind_groups.prefilter_bind_group,
+                &dynamic_offsets,
+            );
+
+            // Downsample chain: progressively halve resolution down to the
+            // smallest mip, 13-tap filtering each step to avoid fireflies.
+            for mip in 0..mip_count - 1 {
+                run_fullscreen_pass(
+                    render_context,
+                    "bloom_downsample",
+                    &bloom_textures.downsample_mip_views[(mip + 1) as usize],
+                    LoadOp::Clear(Color::BLACK.into()),
+                    &bloom_shaders.downsample_pipeline,
+                    &bloom_bind_groups.downsample_bind_groups[mip as usize],
+                    &dynamic_offsets,
+                );
+            }
+
+            // Upsample chain: tent-filter each level and additively blend it
+            // into the next-larger mip, which already holds that mip's
+            // downsampled content.
+            for mip in (0..mip_count - 1).rev() {
+                // Upsample mip `m` is half the size of downsample mip `m`
+                // (the upsample chain starts at `width >> 1`), so the base
+                // content it's seeded with has to come from downsample mip
+                // `m + 1`, at that mip's extent.
+                render_context.command_encoder.copy_texture_to_texture(
+                    ImageCopyTexture {
+                        texture: &bloom_textures.downsample_texture,
+                        mip_level: mip + 1,
+                        origin: Origin3d::ZERO,
+                        aspect: TextureAspect::All,
+                    },
+                    ImageCopyTexture {
+                        texture: &bloom_textures.upsample_texture,
+                        mip_level: mip,
+                        origin: Origin3d::ZERO,
+                        aspect: TextureAspect::All,
+                    },
+                    bloom_textures.mip_extents[(mip + 1) as usize],
+                );
+                run_fullscreen_pass(
+                    render_context,
+                    "bloom_upsample",
+                    &bloom_textures.upsample_mip_views[mip as usize],
+                    LoadOp::Load,
+                    &bloom_shaders.upsample_pipeline,
+                    &bloom_bind_groups.upsample_bind_groups[mip as usize],
+                    &dynamic_offsets,
+                );
+            }
+
             let pass_descriptor = RenderPassDescriptor {
                 label: Some("uber"),
                 color_attachments: &[RenderPassColorAttachment {
@@ -350,71 +1101,297 @@ impl Node for UberPassNode {
                 depth_stencil_attachment: None,
             };
 
-            let draw_functions = world.get_resource::<DrawFunctions>().unwrap();
-
             let render_pass = render_context
                 .command_encoder
                 .begin_render_pass(&pass_descriptor);
-            let mut draw_functions = draw_functions.write();
             let mut tracked_pass = TrackedRenderPass::new(render_pass);
-            for drawable in uber_phase.drawn_things.iter() {
-                let draw_function = draw_functions.get_mut(drawable.draw_function).unwrap();
-                draw_function.draw(
-                    world,
-                    &mut tracked_pass,
-                    view_entity,
-                    drawable.draw_key,
-                    drawable.sort_key,
-                );
-            }
+            uber_phase.render(&mut tracked_pass, world, view_entity);
         }
 
         Ok(())
     }
 }
 
-type DrawUberParams<'s, 'w> = (
-    Res<'w, UberEffectShaders>,
-    Res<'w, UberConfigBindGroup>,
-    Query<'w, 's, (&'w ViewUniformOffset, &'w UberViewBindGroup)>,
-);
+/// Sets the single uber fullscreen pipeline; unlike a mesh `RenderCommand`
+/// this doesn't vary per-item, since every `UberPhase` draw uses it.
+pub struct SetUberPipeline;
+
+impl RenderCommand<UberPhase> for SetUberPipeline {
+    type Param = Res<'static, UberEffectShaders>;
 
-pub struct DrawUber {
-    params: SystemState<DrawUberParams<'static, 'static>>,
+    fn render<'w>(
+        _view: Entity,
+        item: &UberPhase,
+        uber_shaders: Res<'w, UberEffectShaders>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) {
+        let uber_shaders = uber_shaders.into_inner();
+        let pipeline = if item.hdr {
+            &uber_shaders.pipeline_hdr
+        } else {
+            &uber_shaders.pipeline_ldr
+        };
+        pass.set_render_pipeline(pipeline);
+    }
 }
 
-impl DrawUber {
-    pub fn new(world: &mut World) -> Self {
-        Self {
-            params: SystemState::new(world),
-        }
+/// Binds group `I`'s scene color texture, read from the item's view entity.
+pub struct SetUberSceneBindGroup<const I: usize>;
+
+impl<const I: usize> RenderCommand<UberPhase> for SetUberSceneBindGroup<I> {
+    type Param = Query<'static, 'static, &'static UberSceneBindGroup>;
+
+    fn render<'w>(
+        _view: Entity,
+        item: &UberPhase,
+        scene_bind_groups: Query<'w, '_, &'static UberSceneBindGroup>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) {
+        let scene_bind_group = scene_bind_groups.get(item.entity()).unwrap();
+        pass.set_bind_group(I, &scene_bind_group.scene_bind_group, &[]);
     }
 }
 
-impl Draw for DrawUber {
-    fn draw<'w>(
-        &mut self,
-        world: &'w World,
+/// Binds group `I`'s uber config (uniform + composited bloom texture),
+/// offsetting into the shared multi-view uniform buffer per the item's view.
+pub struct SetUberConfigBindGroup<const I: usize>;
+
+impl<const I: usize> RenderCommand<UberPhase> for SetUberConfigBindGroup<I> {
+    type Param = Query<'static, 'static, (&'static UberConfigBindGroup, &'static UberUniformOffset)>;
+
+    fn render<'w>(
+        _view: Entity,
+        item: &UberPhase,
+        config_bind_groups: Query<'w, '_, (&'static UberConfigBindGroup, &'static UberUniformOffset)>,
         pass: &mut TrackedRenderPass<'w>,
-        view: Entity,
-        _draw_key: usize,
-        _sort_key: usize,
     ) {
-        let (uber_shaders, uber_config_bind_group, views) = self.params.get(world);
-        let (view_uniform_offset, uber_view_bind_group) = views.get(view).unwrap();
-        pass.set_render_pipeline(&uber_shaders.into_inner().pipeline);
+        let (config_bind_group, uniform_offset) = config_bind_groups.get(item.entity()).unwrap();
         pass.set_bind_group(
-            0,
-            &uber_view_bind_group.view_bind_group,
-            &[view_uniform_offset.offset],
+            I,
+            &config_bind_group.uber_config_bind_group,
+            &[uniform_offset.offset],
         );
+    }
+}
 
-        pass.set_bind_group(
-            1,
-            &uber_config_bind_group.into_inner().uber_config_bind_group,
-            &[],
-        );
+/// Draws the fullscreen triangle every uber-style pass in this crate uses;
+/// `uber.wgsl`'s vertex stage derives its UV purely from the vertex index.
+pub struct DrawFullscreenTriangle;
+
+impl RenderCommand<UberPhase> for DrawFullscreenTriangle {
+    type Param = ();
 
+    fn render<'w>(_view: Entity, _item: &UberPhase, _param: (), pass: &mut TrackedRenderPass<'w>) {
         pass.draw(0..3, 0..1);
     }
 }
+
+/// The uber pass's draw function: composed from small, reusable
+/// `RenderCommand`s instead of one hand-written `Draw` impl, so downstream
+/// crates can insert extra commands (e.g. more bind groups) without
+/// rewriting the whole draw.
+pub type DrawUber = (
+    SetUberPipeline,
+    SetUberSceneBindGroup<0>,
+    SetUberConfigBindGroup<1>,
+    DrawFullscreenTriangle,
+);
+
+/// Pipelines for the upscale pass: `blit` (sampled with either a nearest or
+/// linear sampler, for the `Nearest`/`Bilinear` filters) and `sharpen` (an
+/// unsharp mask on top of a linear sample, for the `Sharpen` filter). Each is
+/// built for both the HDR and LDR-fallback target format, like
+/// `UberEffectShaders`, since this pass writes into the view's own target and
+/// must match whichever format that view's `Hdr` component selected.
+pub struct UpscaleEffectShaders {
+    blit_pipeline_hdr: RenderPipeline,
+    blit_pipeline_ldr: RenderPipeline,
+    sharpen_pipeline_hdr: RenderPipeline,
+    sharpen_pipeline_ldr: RenderPipeline,
+    input_layout: BindGroupLayout,
+    nearest_sampler: Sampler,
+    linear_sampler: Sampler,
+}
+
+impl FromWorld for UpscaleEffectShaders {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.get_resource::<RenderDevice>().unwrap();
+
+        let upscale_shader = Shader::from_wgsl(include_str!("upscale.wgsl"));
+        let upscale_shader_module = render_device.create_shader_module(&upscale_shader);
+
+        let input_entries = texture_sampler_entries(0);
+        let input_layout = render_device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            entries: &input_entries,
+            label: None,
+        });
+
+        let pipeline_layout = render_device.create_pipeline_layout(&PipelineLayoutDescriptor {
+            label: None,
+            push_constant_ranges: &[],
+            bind_group_layouts: &[&input_layout],
+        });
+
+        let primitive = PrimitiveState {
+            topology: PrimitiveTopology::TriangleList,
+            strip_index_format: None,
+            front_face: FrontFace::Ccw,
+            cull_mode: Some(Face::Back),
+            polygon_mode: PolygonMode::Fill,
+            clamp_depth: false,
+            conservative: false,
+        };
+
+        let make_pipeline = |entry_point: &'static str, format: TextureFormat| {
+            render_device.create_render_pipeline(&RenderPipelineDescriptor {
+                label: None,
+                vertex: VertexState {
+                    buffers: &[],
+                    module: &upscale_shader_module,
+                    entry_point: "vertex",
+                },
+                fragment: Some(FragmentState {
+                    module: &upscale_shader_module,
+                    entry_point,
+                    // Writes directly into the view's own full-resolution
+                    // target, so this has to match that view's `Hdr`-selected
+                    // format exactly, not just assume HDR.
+                    targets: &[ColorTargetState {
+                        format,
+                        blend: None,
+                        write_mask: ColorWrite::ALL,
+                    }],
+                }),
+                depth_stencil: None,
+                layout: Some(&pipeline_layout),
+                multisample: MultisampleState::default(),
+                primitive,
+            })
+        };
+
+        UpscaleEffectShaders {
+            blit_pipeline_hdr: make_pipeline("blit", uber_target_format(true)),
+            blit_pipeline_ldr: make_pipeline("blit", uber_target_format(false)),
+            sharpen_pipeline_hdr: make_pipeline("sharpen", uber_target_format(true)),
+            sharpen_pipeline_ldr: make_pipeline("sharpen", uber_target_format(false)),
+            input_layout,
+            nearest_sampler: render_device.create_sampler(&SamplerDescriptor {
+                mag_filter: FilterMode::Nearest,
+                min_filter: FilterMode::Nearest,
+                mipmap_filter: FilterMode::Nearest,
+                ..SamplerDescriptor::default()
+            }),
+            linear_sampler: render_device.create_sampler(&SamplerDescriptor::default()),
+        }
+    }
+}
+
+/// The per-view bind group and filter selection for the upscale pass, built
+/// once `ViewUber` (the low-res source) is available.
+pub struct ViewUpscaleBindGroup {
+    bind_group: BindGroup,
+    filter: UpscaleFilter,
+    // Whether this view's `Hdr` component selects the HDR or LDR-fallback
+    // upscale pipeline; read by `UpscalePassNode`.
+    hdr: bool,
+}
+
+/// Picks the upscale pass's input bind group and sampler from each view's
+/// `RenderScale` (defaulting to `Bilinear` if the view has none, i.e. runs at
+/// full resolution; the pass still executes, now as a same-size blit, to get
+/// `ViewUber` into the view's target).
+pub fn queue_upscale(
+    mut commands: Commands,
+    render_device: Res<RenderDevice>,
+    upscale_shaders: Res<UpscaleEffectShaders>,
+    views: Query<(Entity, &ViewUber, Option<&RenderScale>, Option<&Hdr>)>,
+) {
+    for (entity, view_uber, render_scale, hdr) in views.iter() {
+        let filter = render_scale.map(|s| s.filter).unwrap_or_default();
+        let sampler = match filter {
+            UpscaleFilter::Nearest => &upscale_shaders.nearest_sampler,
+            UpscaleFilter::Bilinear | UpscaleFilter::Sharpen => &upscale_shaders.linear_sampler,
+        };
+
+        let bind_group = render_device.create_bind_group(&BindGroupDescriptor {
+            entries: &[
+                BindGroupEntry {
+                    binding: 0,
+                    resource: BindingResource::TextureView(&view_uber.view_uber_texture_view),
+                },
+                BindGroupEntry {
+                    binding: 1,
+                    resource: BindingResource::Sampler(sampler),
+                },
+            ],
+            label: None,
+            layout: &upscale_shaders.input_layout,
+        });
+
+        commands.entity(entity).insert(ViewUpscaleBindGroup {
+            bind_group,
+            filter,
+            hdr: hdr.map(|h| h.enabled).unwrap_or(true),
+        });
+    }
+}
+
+/// Runs last in the post-process subgraph: blits (or sharpens) the low-res
+/// `ViewUber` texture up into the view's full-resolution target, split
+/// cleanly out of `UberPassNode` so upscaling stays independent of
+/// post-process quality.
+pub struct UpscalePassNode {
+    main_view_query: QueryState<(&'static ViewTarget, &'static ViewUpscaleBindGroup)>,
+}
+
+impl UpscalePassNode {
+    pub const IN_VIEW: &'static str = "view";
+
+    pub fn new(world: &mut World) -> Self {
+        Self {
+            main_view_query: QueryState::new(world),
+        }
+    }
+}
+
+impl Node for UpscalePassNode {
+    fn input(&self) -> Vec<SlotInfo> {
+        vec![SlotInfo::new(Self::IN_VIEW, SlotType::Entity)]
+    }
+
+    fn update(&mut self, world: &mut World) {
+        self.main_view_query.update_archetypes(world);
+    }
+
+    fn run(
+        &self,
+        graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        let view_entity = graph.get_input_entity(Self::IN_VIEW)?;
+        if let Some((view_target, upscale_bind_group)) =
+            self.main_view_query.get_manual(world, view_entity).ok()
+        {
+            let upscale_shaders = world.get_resource::<UpscaleEffectShaders>().unwrap();
+            let pipeline = match (upscale_bind_group.filter, upscale_bind_group.hdr) {
+                (UpscaleFilter::Sharpen, true) => &upscale_shaders.sharpen_pipeline_hdr,
+                (UpscaleFilter::Sharpen, false) => &upscale_shaders.sharpen_pipeline_ldr,
+                (UpscaleFilter::Nearest | UpscaleFilter::Bilinear, true) => &upscale_shaders.blit_pipeline_hdr,
+                (UpscaleFilter::Nearest | UpscaleFilter::Bilinear, false) => &upscale_shaders.blit_pipeline_ldr,
+            };
+
+            run_fullscreen_pass(
+                render_context,
+                "upscale",
+                view_target.main_texture(),
+                LoadOp::Load,
+                pipeline,
+                &upscale_bind_group.bind_group,
+                &[],
+            );
+        }
+
+        Ok(())
+    }
+}